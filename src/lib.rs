@@ -18,7 +18,7 @@
 //! #![feature(unboxed_closures)]
 //! #![feature(fn_traits)]
 //! 
-//! use fn_macro::{Fn, fn_args, fn_body, fn_output};
+//! use fn_macro::Fn;
 //! 
 //! #[derive(Fn)]
 //! #[fn_args(f64, f64, String)]
@@ -34,99 +34,134 @@
 //!     println!("{}", object(1.0, 2.5, String::from("Hello"))) //Hello 13.0
 //! }
 //! ```
-//! 
-//! ## Known issues
-//! Due to the use of `expect` in the macro code, VSCode will highlight the macro's use as incorrect code, claiming it will always panic. This is wrong - the macro will only panic if one of the necessary fields is not provided.
-
+//!
+//! `#[derive(Fn)]` works on tuple structs, named-field structs, and unit structs alike - the
+//! `#[fn_body{...}]` tokens are spliced into the generated `call` verbatim, so a named-field
+//! struct just refers to its fields by name (`self.offset`) instead of by index (`self.0`).
+//!
+//! By default `call_once` and `call_mut` both delegate to the shared `#[fn_body{...}]` by
+//! calling `self(...)`. If a struct needs to consume a field in `call_once` (e.g. to move a
+//! `String` or `Vec<T>` out of `self`), add `#[fn_once_body{...}]` and/or
+//! `#[fn_mut_body{...}]` - when present, their tokens are spliced into `call_once`/`call_mut`
+//! directly instead of delegating.
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Attribute, DeriveInput, MetaList, parse};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, DeriveInput, Ident, MetaList, Token, Type, parse};
 
 /// The main macro, to begin the derivation process
-/// 
+///
 /// See the top level documentation for more detail
-#[proc_macro_derive(Fn)]
+#[proc_macro_derive(
+    Fn,
+    attributes(fn_args, fn_body, fn_output, fn_once_body, fn_mut_body)
+)]
 pub fn derive_fn_mut(input: TokenStream) -> TokenStream {
-    let ast = parse(input).unwrap();
-    impl_fn(&ast)
+    let ast = match parse::<DeriveInput>(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match impl_fn(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
 
-fn find_attr(attributes: &[Attribute], wanted_attribute: &str) -> Attribute {
+fn find_attr(attributes: &[Attribute], wanted_attribute: &str, ident: &Ident) -> syn::Result<Attribute> {
     attributes
         .iter()
         .find(|attr| attr.path().is_ident(wanted_attribute))
-        .expect("No attribute of type {wanted_attribute} given")
-        .clone()
+        .cloned()
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                ident,
+                format!("#[derive(Fn)] requires a #[{wanted_attribute}(...)] attribute"),
+            )
+        })
 }
 
-fn impl_fn(ast: &DeriveInput) -> TokenStream {
+fn find_attr_opt(attributes: &[Attribute], wanted_attribute: &str) -> Option<Attribute> {
+    attributes
+        .iter()
+        .find(|attr| attr.path().is_ident(wanted_attribute))
+        .cloned()
+}
+
+fn impl_fn(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let name = &ast.ident;
-    let args_attr = find_attr(&ast.attrs, "fn_args");
+    let args_attr = find_attr(&ast.attrs, "fn_args", name)?;
     let MetaList {
         tokens: arg_tokens, ..
-    } = args_attr.meta.require_list().unwrap();
-    let arg_size = arg_tokens.clone().into_iter().count().div_ceil(2);
+    } = args_attr.meta.require_list()?;
+    let arg_types: Vec<Type> = Punctuated::<Type, Token![,]>::parse_terminated
+        .parse2(arg_tokens.clone())?
+        .into_iter()
+        .collect();
+    let arg_size = arg_types.len();
     let args: proc_macro2::TokenStream = (0..arg_size)
         .map(|index| format!("args.{index}"))
         .collect::<Vec<_>>()
         .join(",")
         .parse()
         .unwrap();
+    // A trailing comma is required so `(#arg_tokens)` always parses as a tuple type, even
+    // with exactly one argument - `(f64)` is just `f64` in parens, not a 1-tuple.
+    let arg_tokens = quote! { #(#arg_types,)* };
 
-    let body_attr = find_attr(&ast.attrs, "fn_body");
+    let body_attr = find_attr(&ast.attrs, "fn_body", name)?;
     let MetaList {
         tokens: body_tokens,
         ..
-    } = body_attr.meta.require_list().unwrap();
+    } = body_attr.meta.require_list()?;
 
-    let output_attr = find_attr(&ast.attrs, "fn_output");
+    let output_attr = find_attr(&ast.attrs, "fn_output", name)?;
     let MetaList {
         tokens: output_tokens,
         ..
-    } = output_attr.meta.require_list().unwrap();
+    } = output_attr.meta.require_list()?;
+    let output_type: Type = syn::parse2(output_tokens.clone())?;
+
+    let once_body = match find_attr_opt(&ast.attrs, "fn_once_body") {
+        Some(attr) => {
+            let MetaList {
+                tokens: once_tokens,
+                ..
+            } = attr.meta.require_list()?;
+            once_tokens.clone()
+        }
+        None => quote! { self(#args) },
+    };
+    let mut_body = match find_attr_opt(&ast.attrs, "fn_mut_body") {
+        Some(attr) => {
+            let MetaList {
+                tokens: mut_tokens, ..
+            } = attr.meta.require_list()?;
+            mut_tokens.clone()
+        }
+        None => quote! { self(#args) },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
 
     let generated = quote! {
-        impl FnOnce<(#arg_tokens)> for #name {
-            type Output = #output_tokens;
+        impl #impl_generics FnOnce<(#arg_tokens)> for #name #ty_generics #where_clause {
+            type Output = #output_type;
             extern "rust-call" fn call_once(self, args: (#arg_tokens)) -> Self::Output {
-                self(#args)
+                #once_body
             }
         }
-        impl FnMut<(#arg_tokens)> for #name {
+        impl #impl_generics FnMut<(#arg_tokens)> for #name #ty_generics #where_clause {
             extern "rust-call" fn call_mut(&mut self, args: (#arg_tokens)) -> Self::Output {
-                self(#args)
+                #mut_body
             }
         }
-        impl Fn<(#arg_tokens)> for #name {
+        impl #impl_generics Fn<(#arg_tokens)> for #name #ty_generics #where_clause {
             extern "rust-call" fn call(&self, args: (#arg_tokens)) -> Self::Output {
                 #body_tokens
             }
         }
 
     };
-    generated.into()
-}
-
-/// The macro to hold the input arguments to the function
-///  
-/// See the top-level documentation for more detail
-#[proc_macro_attribute]
-pub fn fn_args(_: TokenStream, item: TokenStream) -> TokenStream {
-    item
-}
-
-/// The macro to hold the body of the function
-/// 
-/// See the top-level documentation for more detail
-#[proc_macro_attribute]
-pub fn fn_body(_: TokenStream, item: TokenStream) -> TokenStream {
-    item
-}
-
-/// The macro to hold the output type of the function
-/// 
-/// See the top-level documentation for more detail
-#[proc_macro_attribute]
-pub fn fn_output(_: TokenStream, item: TokenStream) -> TokenStream {
-    item
+    Ok(generated)
 }
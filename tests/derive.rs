@@ -0,0 +1,83 @@
+#![feature(unboxed_closures)]
+#![feature(fn_traits)]
+
+use fn_macro::Fn;
+
+#[derive(Fn)]
+#[fn_args()]
+#[fn_body{ self.0 }]
+#[fn_output(T)]
+struct GenericIdentity<T>(T)
+where
+    T: Copy;
+
+#[test]
+fn generic_struct_derives() {
+    let identity = GenericIdentity(3.5_f64);
+    assert_eq!(identity(), 3.5);
+}
+
+#[derive(Fn)]
+#[fn_args()]
+#[fn_body{ *self.0 }]
+#[fn_output(f64)]
+struct RefValue<'a>(&'a f64);
+
+#[test]
+fn lifetime_struct_derives() {
+    let base = 3.5;
+    let value = RefValue(&base);
+    assert_eq!(value(), 3.5);
+}
+
+#[derive(Fn)]
+#[fn_args(f64)]
+#[fn_body{ self.0 + args.0 }]
+#[fn_output(f64)]
+struct TupleAdder(f64);
+
+#[derive(Fn)]
+#[fn_args(f64)]
+#[fn_body{ self.offset + args.0 }]
+#[fn_output(f64)]
+struct NamedAdder {
+    offset: f64,
+}
+
+#[derive(Fn)]
+#[fn_args()]
+#[fn_body{ 42 }]
+#[fn_output(i32)]
+struct UnitAnswer;
+
+#[test]
+fn tuple_struct_derives() {
+    let adder = TupleAdder(1.0);
+    assert_eq!(adder(2.0), 3.0);
+}
+
+#[test]
+fn named_struct_derives() {
+    let adder = NamedAdder { offset: 1.0 };
+    assert_eq!(adder(2.0), 3.0);
+}
+
+#[test]
+fn unit_struct_derives() {
+    let answer = UnitAnswer;
+    assert_eq!(answer(), 42);
+}
+
+#[derive(Fn)]
+#[fn_args()]
+#[fn_body{ self.0.clone() }]
+#[fn_once_body{ self.0 }]
+#[fn_output(String)]
+struct Greeting(String);
+
+#[test]
+fn call_once_consumes_field_while_call_borrows() {
+    let greeting = Greeting(String::from("hello"));
+    assert_eq!(Fn::call(&greeting, ()), "hello");
+    assert_eq!(FnOnce::call_once(greeting, ()), "hello");
+}